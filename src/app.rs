@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::PathBuf;
+
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use tui::backend::Backend;
+use tui::Terminal;
+
+use crate::file_ops;
+
+pub struct App<B: Backend> {
+    pub current_directory: PathBuf,
+    pub terminal: Terminal<B>,
+    pub directory_contents: Vec<file_ops::DirectoryItem>,
+    pub selection_index: Option<usize>,
+    pub error: Option<String>,
+    command_buffer: Vec<char>,
+
+    pub picker_active: bool,
+    pub picker_query: String,
+    pub picker_selection: Option<usize>,
+
+    pub size_color_scale: bool,
+
+    pub tree_view_active: bool,
+    pub tree_entries: Vec<(Vec<bool>, file_ops::DirectoryItem)>,
+    pub tree_selection: Option<usize>,
+    tree_max_depth: usize,
+    tree_collapsed: HashSet<String>,
+
+    pub preview_content: Option<file_ops::PreviewContent>,
+    pub preview_extension: String,
+    pub preview_scroll: usize,
+    pub syntax_set: SyntaxSet,
+    pub theme_set: ThemeSet,
+
+    pub filesystems_view_active: bool,
+    pub filesystems: Vec<file_ops::FilesystemInfo>,
+
+    pub aggregate_small_files: bool,
+
+    size_cache: file_ops::SizeCache,
+}
+
+impl<B: Backend> App<B> {
+    pub fn new(terminal: Terminal<B>, current_directory: PathBuf) -> io::Result<Self> {
+        let mut size_cache = file_ops::SizeCache::new();
+        let directory_contents = file_ops::read_directory(&current_directory, &mut size_cache)?;
+
+        Ok(App {
+            current_directory,
+            terminal,
+            directory_contents,
+            selection_index: None,
+            error: None,
+            command_buffer: Vec::new(),
+
+            picker_active: false,
+            picker_query: String::new(),
+            picker_selection: None,
+
+            size_color_scale: false,
+
+            tree_view_active: false,
+            tree_entries: Vec::new(),
+            tree_selection: None,
+            tree_max_depth: 3,
+            tree_collapsed: HashSet::new(),
+
+            preview_content: None,
+            preview_extension: String::new(),
+            preview_scroll: 0,
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme_set: ThemeSet::load_defaults(),
+
+            filesystems_view_active: false,
+            filesystems: Vec::new(),
+
+            aggregate_small_files: false,
+
+            size_cache,
+        })
+    }
+
+    pub fn get_command_buffer_as_string(&self) -> String {
+        self.command_buffer.iter().collect()
+    }
+
+    pub fn push_command_char(&mut self, c: char) {
+        self.command_buffer.push(c);
+    }
+
+    pub fn pop_command_char(&mut self) {
+        self.command_buffer.pop();
+    }
+
+    /// Runs the buffered command, toggling/populating whichever view it
+    /// names, then clears the buffer.
+    pub fn execute_command(&mut self) {
+        let command = self.get_command_buffer_as_string();
+        self.command_buffer.clear();
+
+        match command.as_str() {
+            "find" | "/" => self.toggle_picker(),
+            "tree" => self.toggle_tree_view(),
+            "scale" => self.size_color_scale = !self.size_color_scale,
+            "fs" | "filesystems" => self.toggle_filesystems_view(),
+            "aggregate" | "small" => self.aggregate_small_files = !self.aggregate_small_files,
+            "collapse" | "fold" => self.toggle_collapsed(),
+            _ => self.error = Some(format!("unknown command: {}", command)),
+        }
+    }
+
+    fn toggle_picker(&mut self) {
+        self.picker_active = !self.picker_active;
+        self.picker_query.clear();
+        self.picker_selection = None;
+    }
+
+    pub fn push_picker_char(&mut self, c: char) {
+        self.picker_query.push(c);
+        self.picker_selection = None;
+    }
+
+    pub fn pop_picker_char(&mut self) {
+        self.picker_query.pop();
+        self.picker_selection = None;
+    }
+
+    fn toggle_tree_view(&mut self) {
+        self.tree_view_active = !self.tree_view_active;
+        if self.tree_view_active {
+            if let Ok(entries) = file_ops::read_directory_tree(
+                &self.current_directory,
+                self.tree_max_depth,
+                &self.tree_collapsed,
+                &mut self.size_cache,
+            ) {
+                self.tree_entries = entries;
+            }
+            self.tree_selection = None;
+        }
+    }
+
+    //Folds/unfolds the currently-selected tree row, then re-walks the tree
+    //so tree_entries reflects the new collapsed set
+    fn toggle_collapsed(&mut self) {
+        let path = match self.tree_selection.and_then(|index| self.tree_entries.get(index)) {
+            Some((_, file_ops::DirectoryItem::Directory((path, _)))) => path.clone(),
+            _ => return,
+        };
+
+        if !self.tree_collapsed.remove(&path) {
+            self.tree_collapsed.insert(path);
+        }
+
+        if let Ok(entries) = file_ops::read_directory_tree(
+            &self.current_directory,
+            self.tree_max_depth,
+            &self.tree_collapsed,
+            &mut self.size_cache,
+        ) {
+            self.tree_entries = entries;
+        }
+    }
+
+    fn toggle_filesystems_view(&mut self) {
+        self.filesystems_view_active = !self.filesystems_view_active;
+        if self.filesystems_view_active {
+            if let Ok(filesystems) = file_ops::read_mounted_filesystems() {
+                self.filesystems = filesystems;
+            }
+        }
+    }
+
+    /// Re-reads the preview pane's contents for whatever `selection_index`
+    /// now points at; called whenever the flat-list selection moves.
+    pub fn update_preview(&mut self) {
+        self.preview_scroll = 0;
+
+        let selected = self
+            .selection_index
+            .and_then(|index| self.directory_contents.get(index));
+
+        let path = match selected {
+            Some(file_ops::DirectoryItem::File((path, _, _))) => path.clone(),
+            _ => {
+                self.preview_content = None;
+                self.preview_extension = String::new();
+                return;
+            }
+        };
+
+        self.preview_extension = PathBuf::from(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_string();
+
+        self.preview_content = file_ops::read_preview(&PathBuf::from(&path), file_ops::PREVIEW_MAX_BYTES)
+            .ok();
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
+use std::fs;
+use std::io::{self, Read};
+use std::mem::MaybeUninit;
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+use std::path::PathBuf;
+
+//How much of a file the preview pane reads before giving up on a huge file
+pub const PREVIEW_MAX_BYTES: usize = 64 * 1024;
+
+//Coarse entry classification, used by the drawing layer for indicator/color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Regular,
+    Executable,
+    Symlink,
+    Socket,
+    Fifo,
+}
+
+#[derive(Debug, Clone)]
+pub enum DirectoryItem {
+    File((String, u64, FileKind)),
+    Directory((String, u64)),
+}
+
+//Caches a directory's recursive size by its path, so revisiting it on every
+//redraw doesn't re-walk the whole subtree
+pub type SizeCache = HashMap<String, u64>;
+
+pub fn read_directory(path: &PathBuf, size_cache: &mut SizeCache) -> io::Result<Vec<DirectoryItem>> {
+    let mut items = Vec::new();
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let entry_path = entry.path();
+        let name = entry_path.to_string_lossy().into_owned();
+
+        if metadata.is_dir() {
+            let size = directory_size(&entry_path, size_cache)?;
+            items.push(DirectoryItem::Directory((name, size)));
+        } else {
+            items.push(DirectoryItem::File((name, metadata.len(), classify(&metadata))));
+        }
+    }
+
+    Ok(items)
+}
+
+//Sums file sizes under path depth-first, caching the result in size_cache
+//so sibling redraws and ancestor aggregation don't re-walk it
+pub fn directory_size(path: &PathBuf, size_cache: &mut SizeCache) -> io::Result<u64> {
+    let key = path.to_string_lossy().into_owned();
+    if let Some(&cached) = size_cache.get(&key) {
+        return Ok(cached);
+    }
+
+    let mut total = 0u64;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            total += directory_size(&entry.path(), size_cache)?;
+        } else {
+            total += metadata.len();
+        }
+    }
+
+    size_cache.insert(key, total);
+    Ok(total)
+}
+
+//Depth-first listing of path and its subdirectories, down to max_depth
+//levels; directories in collapsed are listed but not recursed into
+pub fn read_directory_tree(
+    path: &PathBuf,
+    max_depth: usize,
+    collapsed: &HashSet<String>,
+    size_cache: &mut SizeCache,
+) -> io::Result<Vec<(Vec<bool>, DirectoryItem)>> {
+    let mut entries = Vec::new();
+    walk_tree(path, Vec::new(), max_depth, collapsed, size_cache, &mut entries)?;
+    Ok(entries)
+}
+
+fn walk_tree(
+    path: &PathBuf,
+    ancestry: Vec<bool>,
+    max_depth: usize,
+    collapsed: &HashSet<String>,
+    size_cache: &mut SizeCache,
+    entries: &mut Vec<(Vec<bool>, DirectoryItem)>,
+) -> io::Result<()> {
+    let mut children = read_directory(path, size_cache)?;
+    children.sort_by(|a, b| item_path(a).cmp(item_path(b)));
+    let count = children.len();
+
+    for (index, child) in children.into_iter().enumerate() {
+        let mut child_ancestry = ancestry.clone();
+        child_ancestry.push(index + 1 == count);
+
+        let child_path = item_path(&child).to_string();
+        let is_dir = matches!(child, DirectoryItem::Directory(_));
+
+        entries.push((child_ancestry.clone(), child));
+
+        if is_dir && child_ancestry.len() <= max_depth && !collapsed.contains(&child_path) {
+            walk_tree(
+                &PathBuf::from(&child_path),
+                child_ancestry,
+                max_depth,
+                collapsed,
+                size_cache,
+                entries,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+//Result of attempting to read a file for the preview pane
+pub enum PreviewContent {
+    Text(String),
+    Binary,
+}
+
+//Reads up to max_bytes of path for the preview pane. Files containing a NUL
+//byte are reported as Binary; otherwise the valid UTF-8 prefix of the read
+//window is previewed (the window can legitimately end mid-codepoint), and
+//only a buffer with no valid UTF-8 prefix at all is treated as Binary.
+pub fn read_preview(path: &PathBuf, max_bytes: usize) -> io::Result<PreviewContent> {
+    let mut file = fs::File::open(path)?;
+    let mut buffer = vec![0u8; max_bytes];
+    let read = file.read(&mut buffer)?;
+    buffer.truncate(read);
+
+    if buffer.contains(&0) {
+        return Ok(PreviewContent::Binary);
+    }
+
+    match std::str::from_utf8(&buffer) {
+        Ok(text) => Ok(PreviewContent::Text(text.to_string())),
+        Err(err) if err.valid_up_to() > 0 => {
+            let valid = &buffer[..err.valid_up_to()];
+            Ok(PreviewContent::Text(
+                std::str::from_utf8(valid).unwrap().to_string(),
+            ))
+        }
+        Err(_) => Ok(PreviewContent::Binary),
+    }
+}
+
+fn item_path(item: &DirectoryItem) -> &str {
+    match item {
+        DirectoryItem::File((path, _, _)) => path,
+        DirectoryItem::Directory((path, _)) => path,
+    }
+}
+
+//A single row of the mounted-filesystems panel
+#[derive(Debug, Clone)]
+pub struct FilesystemInfo {
+    pub device: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub available_bytes: u64,
+}
+
+//Reads the mount table from /proc/mounts and statvfs's each mount point for
+//its usage, skipping any mount the latter fails on (e.g. a virtual fs with
+//no backing device)
+pub fn read_mounted_filesystems() -> io::Result<Vec<FilesystemInfo>> {
+    let contents = fs::read_to_string("/proc/mounts")?;
+    let mut filesystems = Vec::new();
+
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            continue;
+        }
+
+        let mount_point = unescape_mount_field(fields[1]);
+        if let Some((total_bytes, available_bytes)) = statvfs_bytes(&mount_point) {
+            filesystems.push(FilesystemInfo {
+                device: unescape_mount_field(fields[0]),
+                mount_point,
+                fs_type: fields[2].to_string(),
+                total_bytes,
+                used_bytes: total_bytes.saturating_sub(available_bytes),
+                available_bytes,
+            });
+        }
+    }
+
+    Ok(filesystems)
+}
+
+//proc/mounts octal-escapes space/tab/backslash/newline in device and mount
+//point fields (e.g. \040 for a space); undo that so the real path is shown
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() && bytes[i + 1..i + 4].iter().all(u8::is_ascii_digit) {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap();
+            if let Ok(value) = u8::from_str_radix(octal, 8) {
+                result.push(value);
+                i += 4;
+                continue;
+            }
+        }
+        result.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+fn statvfs_bytes(mount_point: &str) -> Option<(u64, u64)> {
+    let path = CString::new(mount_point).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+
+    let result = unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) };
+    if result != 0 {
+        return None;
+    }
+
+    let stat = unsafe { stat.assume_init() };
+    let block_size = stat.f_frsize as u64;
+    Some((stat.f_blocks as u64 * block_size, stat.f_bavail as u64 * block_size))
+}
+
+//Symlinks and special files take priority over the executable-bit check
+fn classify(metadata: &fs::Metadata) -> FileKind {
+    let file_type = metadata.file_type();
+
+    if file_type.is_symlink() {
+        FileKind::Symlink
+    } else if file_type.is_socket() {
+        FileKind::Socket
+    } else if file_type.is_fifo() {
+        FileKind::Fifo
+    } else if metadata.mode() & 0o111 != 0 {
+        FileKind::Executable
+    } else {
+        FileKind::Regular
+    }
+}
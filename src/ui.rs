@@ -1,7 +1,11 @@
+use std::collections::HashSet;
 use std::io;
 use std::path::PathBuf;
 use std::thread;
 
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Style as SyntectStyle, ThemeSet};
+use syntect::parsing::SyntaxSet;
 use tui::backend::Backend;
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
@@ -11,7 +15,7 @@ use tui::Frame;
 use crate::app::App;
 use crate::file_ops;
 
-pub fn draw(app: &mut App) -> Result<(), io::Error> {
+pub fn draw<B: Backend>(app: &mut App<B>) -> Result<(), io::Error> {
     let command_string = app.get_command_buffer_as_string();
     let mut reset_error = false;
 
@@ -21,6 +25,21 @@ pub fn draw(app: &mut App) -> Result<(), io::Error> {
         directory_contents,
         selection_index,
         error,
+        picker_active,
+        picker_query,
+        picker_selection,
+        size_color_scale,
+        tree_view_active,
+        tree_entries,
+        tree_selection,
+        preview_content,
+        preview_extension,
+        preview_scroll,
+        syntax_set,
+        theme_set,
+        filesystems_view_active,
+        filesystems,
+        aggregate_small_files,
         ..
     } = app;
 
@@ -32,12 +51,58 @@ pub fn draw(app: &mut App) -> Result<(), io::Error> {
             .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
             .split(f.size());
 
-        draw_file_list(
+        if *filesystems_view_active {
+            draw_filesystems(&mut f, chunks[0], filesystems);
+            if let Some(err) = error {
+                draw_error(&mut f, chunks[1], err);
+                reset_error = true;
+            } else {
+                draw_command_buffer(&mut f, chunks[1], command_string);
+            }
+            return;
+        }
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+            .split(chunks[0]);
+
+        if *picker_active {
+            draw_picker(
+                &mut f,
+                main_chunks[0],
+                directory_contents,
+                picker_selection,
+                picker_query,
+            );
+        } else if *tree_view_active {
+            draw_tree(
+                &mut f,
+                main_chunks[0],
+                tree_entries,
+                tree_selection,
+                current_directory,
+            );
+        } else {
+            draw_file_list(
+                &mut f,
+                main_chunks[0],
+                directory_contents,
+                selection_index,
+                current_directory,
+                *size_color_scale,
+                *aggregate_small_files,
+            );
+        }
+
+        draw_preview(
             &mut f,
-            chunks[0],
-            directory_contents,
-            selection_index,
-            current_directory,
+            main_chunks[1],
+            preview_content,
+            preview_extension,
+            *preview_scroll,
+            syntax_set,
+            theme_set,
         );
 
         //Error & command box drawing
@@ -63,6 +128,8 @@ pub fn draw_file_list<B: Backend>(
     files: &Vec<file_ops::DirectoryItem>,
     selected_file: &Option<usize>,
     current_dir: &PathBuf,
+    size_color_scale: bool,
+    aggregate_small: bool,
 ) {
     let mut names: Vec<Text> = Vec::new();
     let mut sizes: Vec<Text> = Vec::new();
@@ -75,44 +142,79 @@ pub fn draw_file_list<B: Backend>(
         .render(frame, area);
 
     if files.len() != 0 {
+        let (min_size, max_size) = file_size_bounds(files);
+        let (shown_files, small_summary) = if aggregate_small {
+            partition_small_files(files, SMALL_FILE_THRESHOLD)
+        } else {
+            (files.iter().collect(), None)
+        };
+
         //Convert DirectoryItems to Text
-        for file in files {
+        for file in shown_files {
             match file {
-                file_ops::DirectoryItem::File((path, size)) => {
+                file_ops::DirectoryItem::File((path, size, kind)) => {
                     let split: Vec<&str> = path.split('/').collect();
-                    let string = String::from(format!("📄 {}\n", split[split.len() - 1 as usize]));
-                    names.push(Text::raw(string));
-                    sizes.push(Text::raw(format!("{}KB\n", size.to_string())));
+                    let (indicator, color) = kind_indicator(*kind);
+                    let string = String::from(format!(
+                        "📄 {}{}\n",
+                        split[split.len() - 1 as usize],
+                        indicator
+                    ));
+                    names.push(Text::styled(string, Style::default().fg(color)));
+
+                    let size_string = format!("{}\n", human_size(*size));
+                    if size_color_scale {
+                        let color = size_scale_color(*size, min_size, max_size);
+                        sizes.push(Text::styled(size_string, Style::default().fg(color)));
+                    } else {
+                        sizes.push(Text::raw(size_string));
+                    }
                 }
-                file_ops::DirectoryItem::Directory(path) => {
+                file_ops::DirectoryItem::Directory((path, size)) => {
                     let split: Vec<&str> = path.split('/').collect();
-                    let string = String::from(format!("📁 {}\n", split[split.len() - 1 as usize]));
-                    names.push(Text::raw(string));
-                    sizes.push(Text::raw("\n"));
+                    let string = String::from(format!("📁 {}/\n", split[split.len() - 1 as usize]));
+                    names.push(Text::styled(string, Style::default().fg(Color::Blue)));
+                    sizes.push(Text::raw(format!("{}\n", human_size(*size))));
                 }
             }
         }
 
-        //Highlight selected file
-        if let Some(selection_index) = selected_file {
-            //Get name of selected file
-            let selected = match &mut names[*selection_index] {
-                Text::Raw(value) => value,
-                _ => "",
+        if let Some((count, total_size)) = small_summary {
+            names.push(Text::raw(format!("<{} files>\n", count)));
+            sizes.push(Text::raw(format!("{}\n", human_size(total_size))));
+        }
+
+        //Highlight selected file, remapping the flat selection index into
+        //row-space when small-file aggregation has folded some rows away
+        let selected_row = selected_file.and_then(|index| {
+            if aggregate_small {
+                map_selection_for_aggregation(files, index, SMALL_FILE_THRESHOLD)
+            } else {
+                Some(index)
+            }
+        });
+
+        if let Some(selection_index) = selected_row {
+            if selection_index < names.len() {
+                //Get name of selected file
+                let selected = match &names[selection_index] {
+                    Text::Raw(value) => value.to_string(),
+                    Text::Styled(value, _) => value.to_string(),
+                    _ => String::new(),
+                };
+
+                //Replace name of selected file with bold name
+                names.insert(
+                    selection_index,
+                    Text::styled(
+                        selected,
+                        Style::default()
+                            .modifier(Modifier::BOLD)
+                            .fg(Color::Indexed(2)),
+                    ),
+                );
+                names.remove(selection_index + 1);
             }
-            .to_string();
-
-            //Replace name of selected file with bold name
-            names.insert(
-                *selection_index,
-                Text::styled(
-                    selected,
-                    Style::default()
-                        .modifier(Modifier::BOLD)
-                        .fg(Color::Indexed(2)),
-                ),
-            );
-            names.remove(selection_index + 1);
         }
 
         //Figure out number of columns and their spacing
@@ -164,6 +266,507 @@ pub fn draw_file_list<B: Backend>(
     }
 }
 
+//Name of the file/directory a DirectoryItem points at
+fn item_name(item: &file_ops::DirectoryItem) -> &str {
+    let path = match item {
+        file_ops::DirectoryItem::File((path, _, _)) => path,
+        file_ops::DirectoryItem::Directory((path, _)) => path,
+    };
+    path.split('/').last().unwrap_or(path)
+}
+
+//Files smaller than this are folded into the <N files> summary row when
+//small-file aggregation is enabled
+const SMALL_FILE_THRESHOLD: u64 = 4096;
+
+//Formats bytes with an adaptive unit (B, KiB, MiB, GiB), picking the largest
+//unit the value still reads as at least 1.0 in; KiB and above get one
+//decimal place, plain bytes are printed as a whole number
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{}B", bytes)
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+//Splits files into entries worth showing individually and a summary of the
+//File entries under threshold bytes, so a directory full of tiny files
+//doesn't drown out what's actually taking up space
+fn partition_small_files(
+    files: &[file_ops::DirectoryItem],
+    threshold: u64,
+) -> (Vec<&file_ops::DirectoryItem>, Option<(usize, u64)>) {
+    let mut shown = Vec::new();
+    let mut small_count = 0;
+    let mut small_total = 0u64;
+
+    for file in files {
+        match file {
+            file_ops::DirectoryItem::File((_, size, _)) if *size < threshold => {
+                small_count += 1;
+                small_total += size;
+            }
+            _ => shown.push(file),
+        }
+    }
+
+    let summary = if small_count > 0 {
+        Some((small_count, small_total))
+    } else {
+        None
+    };
+
+    (shown, summary)
+}
+
+fn is_small_file(file: &file_ops::DirectoryItem, threshold: u64) -> bool {
+    matches!(file, file_ops::DirectoryItem::File((_, size, _)) if *size < threshold)
+}
+
+//Maps a selection index from the full files list into row-space after
+//small-file aggregation: the row of its shown entry, or the trailing
+//summary row if it was folded away. None if the index is out of bounds.
+fn map_selection_for_aggregation(
+    files: &[file_ops::DirectoryItem],
+    selected: usize,
+    threshold: u64,
+) -> Option<usize> {
+    let selected_item = files.get(selected)?;
+    let shown_before = files[..selected]
+        .iter()
+        .filter(|file| !is_small_file(file, threshold))
+        .count();
+
+    if is_small_file(selected_item, threshold) {
+        let shown_total = files.iter().filter(|file| !is_small_file(file, threshold)).count();
+        Some(shown_total)
+    } else {
+        Some(shown_before)
+    }
+}
+
+//Smallest/largest file size among files, ignoring directories; (0, 0) when
+//there are no sized entries
+fn file_size_bounds(files: &[file_ops::DirectoryItem]) -> (u64, u64) {
+    let sizes: Vec<u64> = files
+        .iter()
+        .filter_map(|file| match file {
+            file_ops::DirectoryItem::File((_, size, _)) => Some(*size),
+            file_ops::DirectoryItem::Directory(_) => None,
+        })
+        .collect();
+
+    if sizes.is_empty() {
+        return (0, 0);
+    }
+
+    sizes.iter().fold((u64::max_value(), 0u64), |(min, max), &size| {
+        (min.min(size), max.max(size))
+    })
+}
+
+//Logarithmic scale between min and max, interpolated along the size ramp;
+//falls back to a mid-ramp color when min == max
+fn size_scale_color(size: u64, min: u64, max: u64) -> Color {
+    if max <= min {
+        return size_ramp_color(0.5);
+    }
+
+    let ln_min = ((min + 1) as f64).ln();
+    let ln_max = ((max + 1) as f64).ln();
+    let t = (((size + 1) as f64).ln() - ln_min) / (ln_max - ln_min);
+    size_ramp_color(t as f32)
+}
+
+//Interpolates t (clamped to [0, 1]) along a blue->green->yellow->red ramp
+fn size_ramp_color(t: f32) -> Color {
+    const STOPS: [(f32, (u8, u8, u8)); 4] = [
+        (0.0, (0, 0, 255)),
+        (1.0 / 3.0, (0, 200, 0)),
+        (2.0 / 3.0, (230, 200, 0)),
+        (1.0, (220, 0, 0)),
+    ];
+
+    let t = t.max(0.0).min(1.0);
+    for window in STOPS.windows(2) {
+        let (t0, (r0, g0, b0)) = window[0];
+        let (t1, (r1, g1, b1)) = window[1];
+        if t <= t1 {
+            let span = (t1 - t0).max(f32::EPSILON);
+            let local = (t - t0) / span;
+            let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * local) as u8;
+            return Color::Rgb(lerp(r0, r1), lerp(g0, g1), lerp(b0, b1));
+        }
+    }
+
+    let (_, (r, g, b)) = STOPS[STOPS.len() - 1];
+    Color::Rgb(r, g, b)
+}
+
+//FileKind -> trailing classifier char + color, exa -F style
+fn kind_indicator(kind: file_ops::FileKind) -> (&'static str, Color) {
+    match kind {
+        file_ops::FileKind::Regular => ("", Color::White),
+        file_ops::FileKind::Executable => ("*", Color::Green),
+        file_ops::FileKind::Symlink => ("@", Color::Cyan),
+        file_ops::FileKind::Socket => ("=", Color::Magenta),
+        file_ops::FileKind::Fifo => ("|", Color::Yellow),
+    }
+}
+
+//Subsequence fuzzy-match; None if query isn't a subsequence of candidate,
+//else the score and the matched byte indices
+fn fuzzy_score(query: &str, candidate: &str) -> Option<(i32, Vec<usize>)> {
+    const MATCH_BASE: i32 = 16;
+    const CONSECUTIVE_BONUS: i32 = 8;
+    const BOUNDARY_BONUS: i32 = 12;
+    const GAP_PENALTY: i32 = 1;
+
+    let query_chars: Vec<char> = query.chars().map(|c| c.to_ascii_lowercase()).collect();
+    if query_chars.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let candidate_chars: Vec<(usize, char)> = candidate.char_indices().collect();
+    let mut query_index = 0;
+    let mut score = 0;
+    let mut matched_indices = Vec::new();
+    let mut last_matched_pos: Option<usize> = None;
+    let mut gap = 0;
+
+    for (pos, &(byte_idx, ch)) in candidate_chars.iter().enumerate() {
+        if query_index >= query_chars.len() {
+            break;
+        }
+
+        if ch.to_ascii_lowercase() != query_chars[query_index] {
+            gap += 1;
+            continue;
+        }
+
+        score += MATCH_BASE;
+
+        if last_matched_pos == Some(pos.wrapping_sub(1)) {
+            score += CONSECUTIVE_BONUS;
+        }
+
+        let prev_char = if pos == 0 {
+            None
+        } else {
+            Some(candidate_chars[pos - 1].1)
+        };
+        let is_boundary = match prev_char {
+            None => true,
+            Some(prev) => {
+                matches!(prev, '/' | '_' | '-' | '.') || (prev.is_lowercase() && ch.is_uppercase())
+            }
+        };
+        if is_boundary {
+            score += BOUNDARY_BONUS;
+        }
+
+        score -= gap * GAP_PENALTY;
+        gap = 0;
+
+        matched_indices.push(byte_idx);
+        last_matched_pos = Some(pos);
+        query_index += 1;
+    }
+
+    if query_index == query_chars.len() {
+        Some((score, matched_indices))
+    } else {
+        None
+    }
+}
+
+//Per-character Text spans, styled so matched_indices stand out
+fn highlighted_name(name: &str, matched_indices: &[usize], selected: bool) -> Vec<Text<'static>> {
+    let matched: HashSet<usize> = matched_indices.iter().cloned().collect();
+    let base_style = if selected {
+        Style::default()
+            .modifier(Modifier::BOLD)
+            .fg(Color::Indexed(2))
+    } else {
+        Style::default()
+    };
+    let match_style = Style::default()
+        .modifier(Modifier::BOLD)
+        .fg(Color::Indexed(3));
+
+    name.char_indices()
+        .map(|(byte_idx, ch)| {
+            let style = if matched.contains(&byte_idx) {
+                match_style
+            } else {
+                base_style
+            };
+            Text::styled(ch.to_string(), style)
+        })
+        .collect()
+}
+
+pub fn draw_picker<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    files: &Vec<file_ops::DirectoryItem>,
+    selected_file: &Option<usize>,
+    query: &str,
+) {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Find─{}", query).as_ref())
+        .render(frame, area);
+
+    let inner_rect = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+
+    let mut matches: Vec<(i32, &file_ops::DirectoryItem, Vec<usize>)> = files
+        .iter()
+        .filter_map(|file| {
+            fuzzy_score(query, item_name(file)).map(|(score, indices)| (score, file, indices))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut lines: Vec<Text> = Vec::new();
+    for (row, (_, file, indices)) in matches.iter().enumerate() {
+        let is_selected = selected_file.map_or(false, |selected| selected == row);
+        lines.extend(highlighted_name(item_name(file), indices, is_selected));
+        lines.push(Text::raw("\n"));
+    }
+
+    Paragraph::new(lines.iter())
+        .wrap(false)
+        .render(frame, inner_rect);
+}
+
+//Recursive tree view: each row prefixed with branch glyphs from its
+//ancestry, with the same type indicator/coloring as draw_file_list
+pub fn draw_tree<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    entries: &Vec<(Vec<bool>, file_ops::DirectoryItem)>,
+    selected_file: &Option<usize>,
+    current_dir: &PathBuf,
+) {
+    Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Tree─{}", current_dir.to_str().unwrap()).as_ref())
+        .render(frame, area);
+
+    let inner_rect = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+
+    let mut names: Vec<Text> = Vec::new();
+    let mut sizes: Vec<Text> = Vec::new();
+    for (row, (ancestry, item)) in entries.iter().enumerate() {
+        let prefix = tree_prefix(ancestry);
+        let (emoji, indicator, color, size) = match item {
+            file_ops::DirectoryItem::Directory((_, size)) => ("📁", "/", Color::Blue, *size),
+            file_ops::DirectoryItem::File((_, size, kind)) => {
+                let (indicator, color) = kind_indicator(*kind);
+                ("📄", indicator, color, *size)
+            }
+        };
+
+        let is_selected = selected_file.map_or(false, |selected| selected == row);
+        let style = if is_selected {
+            Style::default()
+                .modifier(Modifier::BOLD)
+                .fg(Color::Indexed(2))
+        } else {
+            Style::default().fg(color)
+        };
+
+        let line = format!(
+            "{}{} {}{}\n",
+            prefix,
+            emoji,
+            item_name(item),
+            indicator
+        );
+        names.push(Text::styled(line, style));
+        sizes.push(Text::raw(format!("{}\n", human_size(size))));
+    }
+
+    Paragraph::new(names.iter())
+        .wrap(false)
+        .render(frame, inner_rect);
+
+    Paragraph::new(sizes.iter())
+        .alignment(Alignment::Right)
+        .wrap(false)
+        .render(
+            frame,
+            Rect {
+                height: inner_rect.height,
+                width: inner_rect.width.saturating_sub(1),
+                x: inner_rect.x,
+                y: inner_rect.y,
+            },
+        );
+}
+
+//Builds the ├─/└─/│  prefix for a tree row from its ancestry: a run of
+//"was this the last child at its level" flags from root to the row itself
+fn tree_prefix(ancestry: &[bool]) -> String {
+    let mut prefix = String::new();
+    if ancestry.is_empty() {
+        return prefix;
+    }
+
+    for &ancestor_is_last in &ancestry[..ancestry.len() - 1] {
+        prefix.push_str(if ancestor_is_last { "   " } else { "│  " });
+    }
+
+    prefix.push_str(if *ancestry.last().unwrap() {
+        "└─ "
+    } else {
+        "├─ "
+    });
+    prefix
+}
+
+//Syntax-highlighted preview of the selected file, scrolled to scroll_offset
+//lines; non-text files fall back to a plain "not previewable" message
+pub fn draw_preview<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    preview: &Option<file_ops::PreviewContent>,
+    extension: &str,
+    scroll_offset: usize,
+    syntax_set: &SyntaxSet,
+    theme_set: &ThemeSet,
+) {
+    Block::default()
+        .borders(Borders::ALL)
+        .title("Preview")
+        .render(frame, area);
+
+    let inner_rect = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+
+    let text = match preview {
+        Some(file_ops::PreviewContent::Text(text)) => text,
+        Some(file_ops::PreviewContent::Binary) | None => {
+            let message = vec![Text::styled(
+                "binary / not previewable",
+                Style::default().fg(Color::DarkGray),
+            )];
+            Paragraph::new(message.iter())
+                .wrap(false)
+                .render(frame, inner_rect);
+            return;
+        }
+    };
+
+    let syntax = syntax_set
+        .find_syntax_by_extension(extension)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, &theme_set.themes["base16-ocean.dark"]);
+
+    //Every line is fed through the highlighter from the start, since it's
+    //stateful and tracks multi-line constructs (block comments, strings)
+    //across calls; only lines at or past scroll_offset are kept for display
+    let mut lines: Vec<Text> = Vec::new();
+    for (row, line) in text.lines().enumerate() {
+        let highlighted = highlighter.highlight(line, syntax_set);
+        if row < scroll_offset {
+            continue;
+        }
+        for (style, token) in highlighted {
+            lines.push(Text::styled(token.to_string(), syntect_to_tui_style(style)));
+        }
+        lines.push(Text::raw("\n"));
+    }
+
+    Paragraph::new(lines.iter())
+        .wrap(false)
+        .render(frame, inner_rect);
+}
+
+//Maps a syntect token style to the nearest tui style: just the foreground
+fn syntect_to_tui_style(style: SyntectStyle) -> Style {
+    Style::default().fg(Color::Rgb(
+        style.foreground.r,
+        style.foreground.g,
+        style.foreground.b,
+    ))
+}
+
+//Mounted-filesystems panel: one row per mount with device, mount point,
+//fs type, and a usage bar colored green->yellow->red as it fills up
+pub fn draw_filesystems<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    filesystems: &Vec<file_ops::FilesystemInfo>,
+) {
+    const BAR_WIDTH: usize = 24;
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+    Block::default()
+        .borders(Borders::ALL)
+        .title("Filesystems")
+        .render(frame, area);
+
+    let inner_rect = Rect::new(area.x + 1, area.y + 1, area.width - 2, area.height - 2);
+
+    let mut lines: Vec<Text> = Vec::new();
+    for fs in filesystems {
+        let ratio = if fs.total_bytes == 0 {
+            0.0
+        } else {
+            fs.used_bytes as f32 / fs.total_bytes as f32
+        };
+
+        lines.push(Text::raw(format!(
+            "{} on {} ({})\n",
+            fs.device, fs.mount_point, fs.fs_type
+        )));
+        lines.push(Text::raw("["));
+        lines.push(Text::styled(
+            usage_bar(ratio, BAR_WIDTH),
+            Style::default().fg(usage_color(ratio)),
+        ));
+        lines.push(Text::raw(format!(
+            "] {:.1}GiB / {:.1}GiB\n\n",
+            fs.used_bytes as f64 / GIB,
+            fs.total_bytes as f64 / GIB
+        )));
+    }
+
+    Paragraph::new(lines.iter())
+        .wrap(false)
+        .render(frame, inner_rect);
+}
+
+//Bar_width-wide bar of block characters; filled portion is
+//round(ratio * bar_width) long, with ratio clamped to [0, 1]
+fn usage_bar(ratio: f32, bar_width: usize) -> String {
+    let filled = (ratio.max(0.0).min(1.0) * bar_width as f32).round() as usize;
+    let filled = filled.min(bar_width);
+    format!("{}{}", "█".repeat(filled), "░".repeat(bar_width - filled))
+}
+
+//Colors a usage bar green->yellow->red as the ratio climbs
+fn usage_color(ratio: f32) -> Color {
+    if ratio < 0.7 {
+        Color::Green
+    } else if ratio < 0.9 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
 pub fn draw_command_buffer<B: Backend>(frame: &mut Frame<B>, area: Rect, command_string: String) {
     let text: Vec<Text> = vec![Text::raw(command_string)];
 
@@ -187,3 +790,129 @@ pub fn draw_error<B: Backend>(frame: &mut Frame<B>, area: Rect, error: &String)
         )
         .render(frame, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_requires_in_order_subsequence() {
+        assert!(fuzzy_score("abc", "acb").is_none());
+        assert!(fuzzy_score("xyz", "abc").is_none());
+    }
+
+    #[test]
+    fn fuzzy_score_matches_subsequence() {
+        let (_, indices) = fuzzy_score("mn", "main.rs").unwrap();
+        assert_eq!(indices, vec![0, 3]);
+    }
+
+    #[test]
+    fn fuzzy_score_rewards_consecutive_and_boundary_matches() {
+        let (consecutive, _) = fuzzy_score("ma", "main.rs").unwrap();
+        let (scattered, _) = fuzzy_score("mn", "main.rs").unwrap();
+        assert!(consecutive > scattered);
+
+        let (boundary, _) = fuzzy_score("f", "src/file_ops.rs").unwrap();
+        let (mid_word, _) = fuzzy_score("i", "src/file_ops.rs").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn fuzzy_score_empty_query_matches_everything() {
+        assert_eq!(fuzzy_score("", "anything"), Some((0, Vec::new())));
+    }
+
+    #[test]
+    fn file_size_bounds_empty_is_zero() {
+        assert_eq!(file_size_bounds(&[]), (0, 0));
+    }
+
+    #[test]
+    fn file_size_bounds_ignores_directories() {
+        let files = vec![
+            file_ops::DirectoryItem::Directory(("big-dir".to_string(), 1_000_000)),
+            file_ops::DirectoryItem::File(("a".to_string(), 10, file_ops::FileKind::Regular)),
+            file_ops::DirectoryItem::File(("b".to_string(), 100, file_ops::FileKind::Regular)),
+        ];
+        assert_eq!(file_size_bounds(&files), (10, 100));
+    }
+
+    #[test]
+    fn size_scale_color_clamps_to_ramp_ends() {
+        assert_eq!(size_scale_color(0, 0, 100), size_ramp_color(0.0));
+        assert_eq!(size_scale_color(100, 0, 100), size_ramp_color(1.0));
+    }
+
+    #[test]
+    fn size_scale_color_falls_back_when_min_equals_max() {
+        assert_eq!(size_scale_color(42, 42, 42), size_ramp_color(0.5));
+    }
+
+    #[test]
+    fn size_ramp_color_interpolates_between_stops() {
+        assert_eq!(size_ramp_color(0.0), Color::Rgb(0, 0, 255));
+        assert_eq!(size_ramp_color(1.0), Color::Rgb(220, 0, 0));
+        assert_eq!(size_ramp_color(-1.0), size_ramp_color(0.0));
+        assert_eq!(size_ramp_color(2.0), size_ramp_color(1.0));
+    }
+
+    #[test]
+    fn tree_prefix_root_is_empty() {
+        assert_eq!(tree_prefix(&[]), "");
+    }
+
+    #[test]
+    fn tree_prefix_marks_last_child_and_ancestry() {
+        assert_eq!(tree_prefix(&[true]), "└─ ");
+        assert_eq!(tree_prefix(&[false]), "├─ ");
+        assert_eq!(tree_prefix(&[true, false]), "   ├─ ");
+        assert_eq!(tree_prefix(&[false, true]), "│  └─ ");
+    }
+
+    #[test]
+    fn usage_bar_fills_proportionally() {
+        assert_eq!(usage_bar(0.0, 10), "░".repeat(10));
+        assert_eq!(usage_bar(1.0, 10), "█".repeat(10));
+        assert_eq!(usage_bar(0.5, 10), format!("{}{}", "█".repeat(5), "░".repeat(5)));
+    }
+
+    #[test]
+    fn usage_bar_clamps_out_of_range_ratios() {
+        assert_eq!(usage_bar(-1.0, 4), usage_bar(0.0, 4));
+        assert_eq!(usage_bar(2.0, 4), usage_bar(1.0, 4));
+    }
+
+    #[test]
+    fn human_size_picks_largest_fitting_unit() {
+        assert_eq!(human_size(0), "0B");
+        assert_eq!(human_size(512), "512B");
+        assert_eq!(human_size(2048), "2.0KiB");
+        assert_eq!(human_size(1024 * 1024), "1.0MiB");
+        assert_eq!(human_size(1024 * 1024 * 1024 * 3), "3.0GiB");
+    }
+
+    fn file(name: &str, size: u64) -> file_ops::DirectoryItem {
+        file_ops::DirectoryItem::File((name.to_string(), size, file_ops::FileKind::Regular))
+    }
+
+    #[test]
+    fn map_selection_for_aggregation_keeps_shown_entries_in_order() {
+        let files = vec![file("a", 10_000), file("tiny", 1), file("b", 20_000)];
+        assert_eq!(map_selection_for_aggregation(&files, 0, 4096), Some(0));
+        assert_eq!(map_selection_for_aggregation(&files, 2, 4096), Some(1));
+    }
+
+    #[test]
+    fn map_selection_for_aggregation_points_folded_entries_at_summary_row() {
+        let files = vec![file("a", 10_000), file("tiny", 1), file("tiny2", 1)];
+        assert_eq!(map_selection_for_aggregation(&files, 1, 4096), Some(1));
+        assert_eq!(map_selection_for_aggregation(&files, 2, 4096), Some(1));
+    }
+
+    #[test]
+    fn map_selection_for_aggregation_out_of_bounds_is_none() {
+        let files = vec![file("a", 100)];
+        assert_eq!(map_selection_for_aggregation(&files, 5, 4096), None);
+    }
+}